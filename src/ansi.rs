@@ -0,0 +1,259 @@
+//! Rendering support for files that already contain raw ANSI escape sequences
+//! (captured terminal sessions, `.ans` art, colorized build logs saved to
+//! disk). Feeding these straight into syntect garbles them, so this module
+//! offers two alternative paths: a safe "escape" mode that makes control
+//! bytes visible instead of corrupting the TUI, and an "ansi" mode that
+//! interprets SGR color/attribute codes directly into ratatui spans.
+
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+
+/// Returns true if `raw` contains ESC (`0x1b`) or other C0 control bytes
+/// that would corrupt the TUI if passed through untouched (tabs, newlines,
+/// and carriage returns are fine and excluded).
+pub fn has_control_bytes(raw: &str) -> bool {
+    raw.bytes()
+        .any(|b| b == 0x1b || (b < 0x20 && b != b'\t' && b != b'\n' && b != b'\r'))
+}
+
+/// Plain-text fallback: render the file as-is but substitute visible
+/// placeholders for control bytes so nothing corrupts the terminal.
+pub fn escape_to_tui_lines(raw: &str) -> Vec<Line<'static>> {
+    let mut out = Vec::new();
+    for line in raw.lines() {
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        let mut plain = String::new();
+        for ch in line.chars() {
+            let b = ch as u32;
+            if ch == '\x1b' {
+                flush_plain(&mut spans, &mut plain);
+                spans.push(Span::styled(
+                    "^[".to_string(),
+                    Style::default().fg(Color::Magenta),
+                ));
+            } else if b < 0x20 && ch != '\t' {
+                flush_plain(&mut spans, &mut plain);
+                spans.push(Span::styled(
+                    format!("\\x{:02x}", b),
+                    Style::default().fg(Color::Magenta),
+                ));
+            } else {
+                plain.push(ch);
+            }
+        }
+        flush_plain(&mut spans, &mut plain);
+        out.push(Line::from(spans));
+    }
+    if out.is_empty() {
+        out.push(Line::from(Span::raw("")));
+    }
+    out
+}
+
+fn flush_plain(spans: &mut Vec<Span<'static>>, plain: &mut String) {
+    if !plain.is_empty() {
+        spans.push(Span::raw(std::mem::take(plain)));
+    }
+}
+
+/// Parse existing SGR (`ESC [ ... m`) sequences in `raw` into styled
+/// ratatui spans, bypassing syntect entirely. Unsupported escape sequences
+/// (cursor movement, clear screen, etc.) are silently dropped rather than
+/// echoed, since they carry no meaning once the file is scrolled statically.
+pub fn ansi_to_tui_lines(raw: &str) -> Vec<Line<'static>> {
+    let mut out = Vec::new();
+    for line in raw.lines() {
+        out.push(ansi_line_to_spans(line));
+    }
+    if out.is_empty() {
+        out.push(Line::from(Span::raw("")));
+    }
+    out
+}
+
+fn ansi_line_to_spans(line: &str) -> Line<'static> {
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    let mut style = Style::default();
+    let mut plain = String::new();
+    let bytes = line.as_bytes();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == 0x1b && bytes.get(i + 1) == Some(&b'[') {
+            // Find the terminating byte of the CSI sequence (0x40..=0x7e).
+            let start = i + 2;
+            let mut end = start;
+            while end < bytes.len() && !(0x40..=0x7e).contains(&bytes[end]) {
+                end += 1;
+            }
+            if end < bytes.len() {
+                if !plain.is_empty() {
+                    spans.push(Span::styled(std::mem::take(&mut plain), style));
+                }
+                if bytes[end] == b'm' {
+                    apply_sgr(&line[start..end], &mut style);
+                }
+                i = end + 1;
+                continue;
+            }
+        }
+        // Safe: iterate by char boundaries, not raw bytes, for the common path.
+        let ch = line[i..].chars().next().unwrap_or('\u{FFFD}');
+        plain.push(ch);
+        i += ch.len_utf8();
+    }
+
+    if !plain.is_empty() {
+        spans.push(Span::styled(plain, style));
+    }
+    Line::from(spans)
+}
+
+fn apply_sgr(params: &str, style: &mut Style) {
+    let codes: Vec<i32> = params
+        .split(';')
+        .map(|p| p.parse::<i32>().unwrap_or(0))
+        .collect();
+    let codes = if codes.is_empty() { vec![0] } else { codes };
+
+    let mut i = 0;
+    while i < codes.len() {
+        match codes[i] {
+            0 => *style = Style::default(),
+            1 => *style = style.add_modifier(Modifier::BOLD),
+            4 => *style = style.add_modifier(Modifier::UNDERLINED),
+            7 => *style = style.add_modifier(Modifier::REVERSED),
+            22 => *style = style.remove_modifier(Modifier::BOLD),
+            24 => *style = style.remove_modifier(Modifier::UNDERLINED),
+            27 => *style = style.remove_modifier(Modifier::REVERSED),
+            30..=37 => *style = style.fg(ansi_16_color(codes[i] - 30)),
+            39 => *style = style.fg(Color::Reset),
+            40..=47 => *style = style.bg(ansi_16_color(codes[i] - 40)),
+            49 => *style = style.bg(Color::Reset),
+            90..=97 => *style = style.fg(ansi_16_bright_color(codes[i] - 90)),
+            100..=107 => *style = style.bg(ansi_16_bright_color(codes[i] - 100)),
+            38 | 48 => {
+                let is_fg = codes[i] == 38;
+                if codes.get(i + 1) == Some(&5) {
+                    if let Some(&idx) = codes.get(i + 2) {
+                        let color = Color::Indexed(idx as u8);
+                        *style = if is_fg {
+                            style.fg(color)
+                        } else {
+                            style.bg(color)
+                        };
+                    }
+                    i += 2;
+                } else if codes.get(i + 1) == Some(&2) {
+                    if let (Some(&r), Some(&g), Some(&b)) =
+                        (codes.get(i + 2), codes.get(i + 3), codes.get(i + 4))
+                    {
+                        let color = Color::Rgb(r as u8, g as u8, b as u8);
+                        *style = if is_fg {
+                            style.fg(color)
+                        } else {
+                            style.bg(color)
+                        };
+                    }
+                    i += 4;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+}
+
+fn ansi_16_color(n: i32) -> Color {
+    match n {
+        0 => Color::Black,
+        1 => Color::Red,
+        2 => Color::Green,
+        3 => Color::Yellow,
+        4 => Color::Blue,
+        5 => Color::Magenta,
+        6 => Color::Cyan,
+        _ => Color::Gray,
+    }
+}
+
+fn ansi_16_bright_color(n: i32) -> Color {
+    match n {
+        0 => Color::DarkGray,
+        1 => Color::LightRed,
+        2 => Color::LightGreen,
+        3 => Color::LightYellow,
+        4 => Color::LightBlue,
+        5 => Color::LightMagenta,
+        6 => Color::LightCyan,
+        _ => Color::White,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain_text(line: &Line<'static>) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn has_control_bytes_ignores_tabs_and_newlines() {
+        assert!(!has_control_bytes("plain\ttext\n"));
+        assert!(has_control_bytes("\x1b[31mred\x1b[0m"));
+        assert!(has_control_bytes("bell\x07"));
+    }
+
+    #[test]
+    fn ansi_line_to_spans_strips_escapes_and_keeps_text() {
+        let line = ansi_line_to_spans("\x1b[31mred\x1b[0m plain");
+        assert_eq!(plain_text(&line), "red plain");
+    }
+
+    #[test]
+    fn ansi_line_to_spans_applies_basic_fg_color() {
+        let line = ansi_line_to_spans("\x1b[32mgreen\x1b[0m");
+        assert_eq!(line.spans.len(), 1);
+        assert_eq!(line.spans[0].style.fg, Some(Color::Green));
+        assert_eq!(line.spans[0].content.as_ref(), "green");
+    }
+
+    #[test]
+    fn apply_sgr_parses_256_color() {
+        let mut style = Style::default();
+        apply_sgr("38;5;202", &mut style);
+        assert_eq!(style.fg, Some(Color::Indexed(202)));
+    }
+
+    #[test]
+    fn apply_sgr_parses_truecolor() {
+        let mut style = Style::default();
+        apply_sgr("38;2;10;20;30", &mut style);
+        assert_eq!(style.fg, Some(Color::Rgb(10, 20, 30)));
+    }
+
+    #[test]
+    fn apply_sgr_reset_clears_style() {
+        let mut style = Style::default().fg(Color::Red).add_modifier(Modifier::BOLD);
+        apply_sgr("0", &mut style);
+        assert_eq!(style, Style::default());
+    }
+
+    #[test]
+    fn unterminated_escape_does_not_panic() {
+        // No terminating byte in 0x40..=0x7e — must not index out of bounds
+        // when scanning to the end of the line looking for one.
+        let line = ansi_line_to_spans("before\x1b[3");
+        assert!(plain_text(&line).starts_with("before"));
+    }
+
+    #[test]
+    fn escape_to_tui_lines_renders_visible_placeholder() {
+        let lines = escape_to_tui_lines("a\x1bb");
+        assert_eq!(lines.len(), 1);
+        assert_eq!(plain_text(&lines[0]), "a^[b");
+    }
+}
@@ -0,0 +1,39 @@
+//! Persistent configuration loaded from the platform config directory via
+//! `directories::ProjectDirs`, the same approach `bat` uses. CLI flags
+//! always override whatever is found here; a missing or unparsable config
+//! file just falls back to built-in defaults.
+
+use directories::ProjectDirs;
+use serde::Deserialize;
+use std::path::PathBuf;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct Config {
+    pub speed_ms: Option<u64>,
+    pub step: Option<usize>,
+    pub exts: Option<String>,
+    pub max_kb: Option<u64>,
+    pub theme: Option<String>,
+}
+
+fn project_dirs() -> Option<ProjectDirs> {
+    ProjectDirs::from("", "", "codescroller")
+}
+
+/// Load `<config dir>/codescroller/config.toml`.
+pub fn load() -> Config {
+    let Some(dirs) = project_dirs() else {
+        return Config::default();
+    };
+    let path = dirs.config_dir().join("config.toml");
+    let Ok(text) = std::fs::read_to_string(&path) else {
+        return Config::default();
+    };
+    toml::from_str(&text).unwrap_or_default()
+}
+
+/// `<config dir>/codescroller/themes/`, scanned for extra `.tmTheme` files
+/// (Catppuccin, Dracula, etc.) to layer on top of syntect's built-ins.
+pub fn themes_dir() -> Option<PathBuf> {
+    project_dirs().map(|d| d.config_dir().join("themes"))
+}
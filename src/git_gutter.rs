@@ -0,0 +1,128 @@
+//! A one-column gutter showing how each rendered line differs from `HEAD`,
+//! the way `bat` does with `git2` + `DiffOptions`. Gated behind the `git`
+//! feature so builds without it pay nothing and binaries stay lean; when the
+//! feature is off (or the path simply isn't under version control) callers
+//! just get `None` and render without a gutter.
+
+use std::path::Path;
+
+/// Status of a single rendered line relative to the indexed/HEAD blob.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LineStatus {
+    Added,
+    Modified,
+    Removed,
+    Unchanged,
+}
+
+/// Raw hunk bounds collected during `diff.foreach`, so statuses can be
+/// computed once the diff has finished walking instead of inside the
+/// (mutually aliasing) callbacks themselves.
+#[cfg(feature = "git")]
+struct DiffHunkInfo {
+    old_lines: u32,
+    new_lines: u32,
+    new_start: u32,
+}
+
+#[cfg(feature = "git")]
+pub fn line_statuses(path: &Path, line_count: usize) -> Option<Vec<LineStatus>> {
+    use git2::{DiffHunk, DiffOptions, Repository};
+
+    let repo = Repository::discover(path.parent()?).ok()?;
+    let workdir = repo.workdir()?;
+    let rel = path.strip_prefix(workdir).ok()?;
+
+    let mut opts = DiffOptions::new();
+    opts.pathspec(rel).context_lines(0);
+
+    // Diff straight against the HEAD tree (not the index) so staged-but-
+    // workdir-unmodified changes still show in the gutter, matching how
+    // `bat` diffs workdir -> HEAD rather than workdir -> index.
+    let head_tree = repo.head().ok()?.peel_to_tree().ok()?;
+    let diff = repo
+        .diff_tree_to_workdir_with_index(Some(&head_tree), Some(&mut opts))
+        .ok()?;
+
+    // `foreach` holds every callback as a live `&mut FnMut` for the whole
+    // call, so a hunk closure and a line closure can't share one `&mut`
+    // status vector without aliasing. Instead each closure only appends to
+    // its own `Vec` of raw events, and the statuses are computed afterward
+    // from those collected hunks/added-lines.
+    let mut hunks: Vec<DiffHunkInfo> = Vec::new();
+    let mut added_lines: Vec<u32> = Vec::new();
+
+    diff.foreach(
+        &mut |_delta, _progress| true,
+        None,
+        Some(&mut |_delta, hunk: DiffHunk| {
+            hunks.push(DiffHunkInfo {
+                old_lines: hunk.old_lines(),
+                new_lines: hunk.new_lines(),
+                new_start: hunk.new_start(),
+            });
+            true
+        }),
+        Some(&mut |_delta, _hunk, line| {
+            if line.origin() == '+' {
+                if let Some(n) = line.new_lineno() {
+                    added_lines.push(n);
+                }
+            }
+            true
+        }),
+    )
+    .ok()?;
+
+    let mut statuses = vec![LineStatus::Unchanged; line_count];
+
+    for hunk in &hunks {
+        if hunk.old_lines > 0 && hunk.new_lines == 0 {
+            // Pure deletion: mark the surviving line just above the removed
+            // block, or the first line when the block was at the top.
+            let boundary = hunk.new_start as usize;
+            if boundary > 0 && boundary - 1 < statuses.len() {
+                statuses[boundary - 1] = LineStatus::Removed;
+            } else if boundary < statuses.len() {
+                statuses[boundary] = LineStatus::Removed;
+            }
+        }
+    }
+
+    for n in added_lines {
+        let idx = n as usize - 1;
+        if idx >= statuses.len() {
+            continue;
+        }
+        // A hunk with both removed and added lines is a replacement
+        // ("modified"); one with only added lines is a pure addition.
+        let hunk_is_replacement = hunks
+            .iter()
+            .find(|h| n >= h.new_start && n < h.new_start + h.new_lines.max(1) && h.new_lines > 0)
+            .is_some_and(|h| h.old_lines > 0 && h.new_lines > 0);
+        statuses[idx] = if hunk_is_replacement {
+            LineStatus::Modified
+        } else {
+            LineStatus::Added
+        };
+    }
+
+    Some(statuses)
+}
+
+#[cfg(not(feature = "git"))]
+pub fn line_statuses(_path: &Path, _line_count: usize) -> Option<Vec<LineStatus>> {
+    None
+}
+
+/// The gutter glyph and color for a given line status; `None` renders as a
+/// blank column so unchanged lines don't draw the eye.
+pub fn gutter_span(status: LineStatus) -> Option<(&'static str, ratatui::style::Color)> {
+    use ratatui::style::Color;
+    match status {
+        LineStatus::Added => Some(("+ ", Color::Green)),
+        LineStatus::Modified => Some(("~ ", Color::Yellow)),
+        LineStatus::Removed => Some(("▁ ", Color::Red)),
+        LineStatus::Unchanged => None,
+    }
+}
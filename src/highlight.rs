@@ -0,0 +1,227 @@
+//! Lazy, windowed syntax highlighting. `syntect`'s line highlighting is
+//! stateful — the parser and highlight state for line N depend on every
+//! line before it — so a viewport window can never be regenerated starting
+//! from the middle of a file. Instead we snapshot that state every
+//! [`CHECKPOINT_INTERVAL`] lines and, when a window is requested, resume
+//! from the nearest checkpoint at or before it rather than from line zero.
+//! This bounds per-frame highlighting work to roughly the viewport height
+//! regardless of file size.
+
+use ratatui::{
+    style::{Color, Style},
+    text::{Line, Span},
+};
+use std::collections::HashMap;
+use syntect::{
+    highlighting::{HighlightIterator, HighlightState, Highlighter, Theme},
+    parsing::{ParseState, ScopeStack, SyntaxReference, SyntaxSet},
+    util::LinesWithEndings,
+};
+
+const CHECKPOINT_INTERVAL: usize = 200;
+
+#[derive(Clone)]
+struct Checkpoint {
+    parse_state: ParseState,
+    highlight_state: HighlightState,
+}
+
+/// The running parser/highlight state immediately after the last line
+/// `ensure_range` processed, kept so the common case — scrolling forward by
+/// a line or a screenful at a time — can resume from exactly where the
+/// previous call left off instead of replaying the whole `CHECKPOINT_INTERVAL`
+/// block every frame.
+struct Frontier {
+    line: usize,
+    parse_state: ParseState,
+    highlight_state: HighlightState,
+}
+
+pub struct IncrementalHighlighter {
+    lines: Vec<String>,
+    checkpoints: Vec<Option<Checkpoint>>,
+    cache: HashMap<usize, Line<'static>>,
+    frontier: Option<Frontier>,
+}
+
+impl IncrementalHighlighter {
+    pub fn new(raw: &str, syntax: &SyntaxReference, theme: &Theme) -> Self {
+        let lines: Vec<String> = LinesWithEndings::from(raw).map(str::to_string).collect();
+        let slot_count = lines.len() / CHECKPOINT_INTERVAL + 1;
+        let mut checkpoints = vec![None; slot_count];
+        checkpoints[0] = Some(Checkpoint {
+            parse_state: ParseState::new(syntax),
+            highlight_state: HighlightState::new(&Highlighter::new(theme), ScopeStack::new()),
+        });
+        Self {
+            lines,
+            checkpoints,
+            cache: HashMap::new(),
+            frontier: None,
+        }
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Highlight and cache every line in `[start, end)`. Resumes from the
+    /// frontier state left by the previous call when it's usable (i.e. it
+    /// covers a line at or before `start`, within the same checkpoint
+    /// block), which is the normal case while scrolling; otherwise falls
+    /// back to the nearest checkpoint at or before `start` so the parser
+    /// state is always correct, never guessed.
+    pub fn ensure_range(&mut self, ps: &SyntaxSet, theme: &Theme, start: usize, end: usize) {
+        let end = end.min(self.lines.len());
+        // Lines already cached need no work: start at the first one that
+        // isn't, so a window overlapping the previous one can resume from
+        // the frontier.
+        let Some(start) = (start..end).find(|i| !self.cache.contains_key(i)) else {
+            return;
+        };
+
+        let checkpoint_idx = start / CHECKPOINT_INTERVAL;
+        let block_start = checkpoint_idx * CHECKPOINT_INTERVAL;
+
+        let (resume_at, mut parse_state, mut highlight_state) = match self.frontier.take() {
+            Some(f) if f.line >= block_start && f.line <= start => {
+                (f.line, f.parse_state, f.highlight_state)
+            }
+            _ => {
+                let checkpoint = self.checkpoint_at(ps, theme, checkpoint_idx);
+                (
+                    block_start,
+                    checkpoint.parse_state,
+                    checkpoint.highlight_state,
+                )
+            }
+        };
+
+        let highlighter = Highlighter::new(theme);
+        for i in resume_at..end {
+            let line = &self.lines[i];
+            let ops = parse_state.parse_line(line, ps).unwrap_or_default();
+            let spans: Vec<Span<'static>> =
+                HighlightIterator::new(&mut highlight_state, &ops, line, &highlighter)
+                    .map(|(style, text)| {
+                        let fg =
+                            Color::Rgb(style.foreground.r, style.foreground.g, style.foreground.b);
+                        Span::styled(text.to_string(), Style::default().fg(fg))
+                    })
+                    .collect();
+            self.cache.insert(i, Line::from(spans));
+        }
+
+        self.frontier = Some(Frontier {
+            line: end,
+            parse_state,
+            highlight_state,
+        });
+    }
+
+    pub fn get(&self, i: usize) -> Option<&Line<'static>> {
+        self.cache.get(&i)
+    }
+
+    /// Return the checkpoint for block `idx` (state at line `idx *
+    /// CHECKPOINT_INTERVAL`), computing and caching it from the previous
+    /// checkpoint if it hasn't been needed yet.
+    fn checkpoint_at(&mut self, ps: &SyntaxSet, theme: &Theme, idx: usize) -> Checkpoint {
+        if let Some(cp) = &self.checkpoints[idx] {
+            return cp.clone();
+        }
+
+        let prev = self.checkpoint_at(ps, theme, idx - 1);
+        let highlighter = Highlighter::new(theme);
+        let mut parse_state = prev.parse_state;
+        let mut highlight_state = prev.highlight_state;
+
+        let start = (idx - 1) * CHECKPOINT_INTERVAL;
+        let end = (idx * CHECKPOINT_INTERVAL).min(self.lines.len());
+        for line in &self.lines[start..end] {
+            let ops = parse_state.parse_line(line, ps).unwrap_or_default();
+            // Consume the iterator purely to advance `highlight_state`.
+            HighlightIterator::new(&mut highlight_state, &ops, line, &highlighter).for_each(drop);
+        }
+
+        let cp = Checkpoint {
+            parse_state,
+            highlight_state,
+        };
+        self.checkpoints[idx] = Some(cp.clone());
+        cp
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> (SyntaxSet, Theme, String) {
+        let ps = SyntaxSet::load_defaults_newlines();
+        let theme = syntect::highlighting::ThemeSet::load_defaults()
+            .themes
+            .get("base16-ocean.dark")
+            .cloned()
+            .unwrap();
+        let mut raw = String::new();
+        for i in 0..(CHECKPOINT_INTERVAL * 3) {
+            raw.push_str(&format!("fn line_{i}() {{ let x = {i}; }}\n"));
+        }
+        (ps, theme, raw)
+    }
+
+    fn plain_text(line: &Line<'static>) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn resuming_from_the_frontier_matches_a_direct_pass() {
+        let (ps, theme, raw) = sample();
+        let syntax = ps.find_syntax_plain_text();
+
+        // One file highlighted incrementally, one line at a time (exercises
+        // the frontier-resume path every call).
+        let mut incremental = IncrementalHighlighter::new(&raw, syntax, &theme);
+        for i in 0..incremental.line_count() {
+            incremental.ensure_range(&ps, &theme, i, i + 1);
+        }
+
+        // The same file highlighted in one shot from a fresh checkpoint.
+        let mut direct = IncrementalHighlighter::new(&raw, syntax, &theme);
+        direct.ensure_range(&ps, &theme, 0, direct.line_count());
+
+        for i in 0..incremental.line_count() {
+            assert_eq!(
+                incremental.get(i).unwrap(),
+                direct.get(i).unwrap(),
+                "line {i} diverged between incremental and direct highlighting"
+            );
+        }
+    }
+
+    #[test]
+    fn jumping_backward_still_highlights_correctly() {
+        let (ps, theme, raw) = sample();
+        let syntax = ps.find_syntax_by_extension("rs").unwrap();
+        let mut h = IncrementalHighlighter::new(&raw, syntax, &theme);
+
+        // Scroll forward past several checkpoint blocks, then jump back near
+        // the start — the frontier is now ahead of `start`, so this must
+        // fall back to the nearest checkpoint rather than use stale state.
+        h.ensure_range(
+            &ps,
+            &theme,
+            CHECKPOINT_INTERVAL * 2,
+            CHECKPOINT_INTERVAL * 2 + 5,
+        );
+        h.ensure_range(&ps, &theme, 1, 4);
+
+        let mut direct = IncrementalHighlighter::new(&raw, syntax, &theme);
+        direct.ensure_range(&ps, &theme, 1, 4);
+
+        for i in 1..4 {
+            assert_eq!(h.get(i).unwrap(), direct.get(i).unwrap());
+        }
+    }
+}
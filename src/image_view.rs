@@ -0,0 +1,199 @@
+//! Terminal image preview for image files encountered in the scroll set,
+//! modeled on how `yazi` drives this: decode with `image`, downscale to fit
+//! the viewport, and emit through whichever graphics protocol the terminal
+//! actually supports, falling back to half-block Unicode art everywhere
+//! else.
+
+use anyhow::{Context, Result};
+use base64::Engine;
+use image::{imageops::FilterType, DynamicImage, GenericImageView};
+use ratatui::{
+    style::{Color, Style},
+    text::{Line, Span},
+};
+use std::path::Path;
+
+/// Graphics protocol to use for inline image rendering, detected once at
+/// startup from the environment so we don't re-probe per frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Protocol {
+    Kitty,
+    Iterm2,
+    Sixel,
+    /// No inline graphics protocol; render with half-block glyphs instead.
+    Unicode,
+}
+
+/// Detect the best available graphics protocol from `$TERM`/`$TERM_PROGRAM`
+/// and the handful of terminal-specific env vars each emulator sets.
+pub fn detect_protocol() -> Protocol {
+    if std::env::var("KITTY_WINDOW_ID").is_ok() {
+        return Protocol::Kitty;
+    }
+    if let Ok(term) = std::env::var("TERM_PROGRAM") {
+        match term.as_str() {
+            "iTerm.app" | "WezTerm" => return Protocol::Iterm2,
+            _ => {}
+        }
+    }
+    if let Ok(term) = std::env::var("TERM") {
+        if term.contains("kitty") {
+            return Protocol::Kitty;
+        }
+        if term.contains("sixel") || term.contains("mlterm") {
+            return Protocol::Sixel;
+        }
+    }
+    if std::env::var("WEZTERM_EXECUTABLE").is_ok() {
+        return Protocol::Iterm2;
+    }
+    Protocol::Unicode
+}
+
+/// A decoded, terminal-ready preview of one image file.
+pub struct ImagePreview {
+    /// Raw protocol payload to print directly to stdout ahead of the frame
+    /// (Kitty/iTerm2); `None` when falling back to `fallback_lines`, which
+    /// includes Sixel terminals since no Sixel encoding is produced.
+    pub escape_payload: Option<String>,
+    /// Half-block Unicode rendering, drawn when there is no
+    /// `escape_payload`.
+    pub fallback_lines: Vec<Line<'static>>,
+}
+
+/// Decode `path`, honor EXIF orientation, and downscale to fit a viewport of
+/// `cell_w`x`cell_h` terminal cells at 1 horizontal and 2 vertical pixels
+/// per cell (the half-block fallback's resolution). The graphics protocol
+/// payloads are encoded from this same downscaled image.
+pub fn load_image(
+    path: &Path,
+    cell_w: u16,
+    cell_h: u16,
+    protocol: Protocol,
+) -> Result<ImagePreview> {
+    let img = image::open(path).with_context(|| format!("decode image {}", path.display()))?;
+    let img = apply_exif_orientation(path, img);
+
+    let target_w = (cell_w as u32).max(1);
+    let target_h = (cell_h.saturating_mul(2) as u32).max(1);
+    let fitted = img.resize(target_w, target_h, FilterType::Lanczos3);
+
+    let fallback_lines = render_half_blocks(&fitted);
+
+    let escape_payload = match protocol {
+        Protocol::Kitty => Some(encode_kitty(&fitted)?),
+        Protocol::Iterm2 => Some(encode_iterm2(&fitted)?),
+        // No vetted pure-Rust Sixel encoder dependency is available here;
+        // render the half-block fallback instead of shipping an unverified
+        // encoding.
+        Protocol::Sixel => None,
+        Protocol::Unicode => None,
+    };
+
+    Ok(ImagePreview {
+        escape_payload,
+        fallback_lines,
+    })
+}
+
+/// Rotate/flip the decoded image per its EXIF `Orientation` tag so portrait
+/// photos aren't shown sideways; images without EXIF data (or without the
+/// `exif` crate finding a tag) pass through unchanged.
+fn apply_exif_orientation(path: &Path, img: DynamicImage) -> DynamicImage {
+    let Ok(file) = std::fs::File::open(path) else {
+        return img;
+    };
+    let mut reader = std::io::BufReader::new(file);
+    let Ok(exif) = exif::Reader::new().read_from_container(&mut reader) else {
+        return img;
+    };
+    let orientation = exif
+        .get_field(exif::Tag::Orientation, exif::In::PRIMARY)
+        .and_then(|f| f.value.get_uint(0))
+        .unwrap_or(1);
+
+    match orientation {
+        2 => img.fliph(),
+        3 => img.rotate180(),
+        4 => img.fliph().rotate180(),
+        5 => img.fliph().rotate270(),
+        6 => img.rotate90(),
+        7 => img.fliph().rotate90(),
+        8 => img.rotate270(),
+        _ => img,
+    }
+}
+
+/// Render an image as two vertically-stacked pixels per cell using the
+/// upper-half-block glyph with distinct fg/bg colors, the same trick
+/// `chafa` uses for graphics-less terminals.
+fn render_half_blocks(img: &DynamicImage) -> Vec<Line<'static>> {
+    let (w, h) = img.dimensions();
+    let rgba = img.to_rgba8();
+    let mut lines = Vec::with_capacity((h as usize + 1) / 2);
+
+    let mut y = 0;
+    while y < h {
+        let mut spans = Vec::with_capacity(w as usize);
+        for x in 0..w {
+            let top = rgba.get_pixel(x, y);
+            let bottom = if y + 1 < h {
+                *rgba.get_pixel(x, y + 1)
+            } else {
+                *top
+            };
+            let fg = Color::Rgb(top[0], top[1], top[2]);
+            let bg = Color::Rgb(bottom[0], bottom[1], bottom[2]);
+            spans.push(Span::styled("▀", Style::default().fg(fg).bg(bg)));
+        }
+        lines.push(Line::from(spans));
+        y += 2;
+    }
+    lines
+}
+
+/// Encode as a Kitty graphics protocol APC: `ESC _G <keys> ; <base64> ESC \`.
+/// `a=T` (transmit+display), `f=32` (raw RGBA). The control keys
+/// (`a=`/`f=`/`s=`/`v=`) belong on the first chunk only — continuation
+/// chunks carry just `m=` — so the terminal doesn't re-interpret `s=`/`v=`
+/// as a second, garbled image.
+fn encode_kitty(img: &DynamicImage) -> Result<String> {
+    let (w, h) = img.dimensions();
+    let rgba = img.to_rgba8().into_raw();
+    let b64 = base64::engine::general_purpose::STANDARD.encode(rgba);
+
+    let mut out = String::new();
+    let mut chunks = b64.as_bytes().chunks(4096).peekable();
+    let mut first = true;
+    while let Some(chunk) = chunks.next() {
+        let more = if chunks.peek().is_some() { 1 } else { 0 };
+        let payload = std::str::from_utf8(chunk).unwrap_or("");
+        if first {
+            out.push_str(&format!(
+                "\x1b_Ga=T,f=32,s={w},v={h},m={more};{payload}\x1b\\"
+            ));
+            first = false;
+        } else {
+            out.push_str(&format!("\x1b_Gm={more};{payload}\x1b\\"));
+        }
+    }
+    Ok(out)
+}
+
+/// Encode as an iTerm2 inline image (`ESC ] 1337 ; File=... : <base64> BEL`).
+fn encode_iterm2(img: &DynamicImage) -> Result<String> {
+    let mut png = Vec::new();
+    img.write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+        .context("encode PNG for iTerm2 preview")?;
+    let b64 = base64::engine::general_purpose::STANDARD.encode(&png);
+    let (w, h) = img.dimensions();
+    Ok(format!(
+        "\x1b]1337;File=inline=1;width={w}px;height={h}px;preserveAspectRatio=1:{b64}\x07"
+    ))
+}
+
+/// Extensions routed to the image preview path instead of syntax
+/// highlighting.
+pub fn is_image_ext(ext: &str) -> bool {
+    matches!(ext, "png" | "jpg" | "jpeg" | "gif" | "webp")
+}
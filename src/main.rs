@@ -1,6 +1,7 @@
 use anyhow::{bail, Context, Result};
 use clap::Parser;
 use crossterm::{
+    cursor::MoveTo,
     event::{self, Event, KeyCode, KeyEventKind},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
@@ -21,43 +22,74 @@ use std::{
     time::{Duration, Instant},
 };
 use syntect::{
-    easy::HighlightLines,
     highlighting::{Theme, ThemeSet},
     parsing::{SyntaxReference, SyntaxSet},
-    util::LinesWithEndings,
 };
 use walkdir::WalkDir;
 
+mod ansi;
+mod config;
+mod git_gutter;
+mod highlight;
+mod image_view;
+mod search;
+mod signals;
+
 #[derive(Parser, Debug)]
-#[command(name = "codescroller", about = "Auto-scroll code files with syntax highlighting.")]
+#[command(
+    name = "codescroller",
+    about = "Auto-scroll code files with syntax highlighting."
+)]
 struct Args {
     /// A file or directory to scroll through
     #[arg(value_name = "PATH")]
     path: PathBuf,
 
-    /// Delay between scroll steps in milliseconds
-    #[arg(long, default_value_t = 60)]
-    speed_ms: u64,
+    /// Delay between scroll steps in milliseconds. Falls back to the config
+    /// file's `speed_ms`, then 60.
+    #[arg(long)]
+    speed_ms: Option<u64>,
 
-    /// Number of terminal lines to advance per tick
-    #[arg(long, default_value_t = 1)]
-    step: usize,
+    /// Number of terminal lines to advance per tick. Falls back to the
+    /// config file's `step`, then 1.
+    #[arg(long)]
+    step: Option<usize>,
 
     /// Loop forever (when reaching end of file list, start over)
     #[arg(long, default_value_t = true)]
     r#loop: bool,
 
-    /// Optional comma-separated extensions (no dots). Example: rs,cs,go,cpp,h,py,js,ts
+    /// Optional comma-separated extensions (no dots). Example: rs,cs,go,cpp,h,py,js,ts.
+    /// Falls back to the config file's `exts`, then a built-in default set.
     #[arg(long)]
     exts: Option<String>,
 
-    /// Maximum file size to load (in KB). Larger files are skipped.
-    #[arg(long, default_value_t = 512)]
-    max_kb: u64,
+    /// Maximum file size to load (in KB). Larger files are skipped. Falls
+    /// back to the config file's `max_kb`, then 512.
+    #[arg(long)]
+    max_kb: Option<u64>,
+
+    /// Name of the syntect theme to use. Falls back to the config file's
+    /// `theme`, then a high-contrast dark default. Cycle live with `t`.
+    #[arg(long)]
+    theme: Option<String>,
 
     /// Start at a random file (requires OS randomness? no; deterministic-ish fallback)
     #[arg(long, default_value_t = false)]
     random_start: bool,
+
+    /// Parse existing ANSI/SGR escape sequences instead of escaping them.
+    /// Auto-enabled for `.ans` and `.log` files even when not passed.
+    #[arg(long, default_value_t = false)]
+    ansi: bool,
+
+    /// How long to hold each image file before advancing, in milliseconds.
+    #[arg(long, default_value_t = 2000)]
+    image_dwell_ms: u64,
+
+    /// Start directly in search mode with this query preset.
+    #[arg(long)]
+    search: Option<String>,
 }
 
 struct App {
@@ -67,8 +99,27 @@ struct App {
     // Current file loaded
     current_path: PathBuf,
     raw: String,
+    total_lines: usize,
+    // Non-empty for modes that don't go through `highlighter` (ansi,
+    // escaped control bytes, image fallback art).
     highlighted_lines: Vec<Line<'static>>,
+    // `Some` only when the current file is being rendered through syntect;
+    // lazily highlights and caches windows as they're scrolled into view.
+    highlighter: Option<highlight::IncrementalHighlighter>,
     syntax_name: String,
+    line_status: Vec<git_gutter::LineStatus>,
+    uses_syntect: bool,
+
+    // Image preview
+    is_image: bool,
+    image_escape_payload: Option<String>,
+    // Whether `image_escape_payload` has already been written to the
+    // terminal for the currently-loaded image; avoids re-transmitting the
+    // whole payload (and flickering against the text frame) every tick.
+    image_payload_sent: bool,
+    image_shown_at: Instant,
+    image_dwell: Duration,
+    graphics_protocol: image_view::Protocol,
 
     scroll: usize,
     paused: bool,
@@ -79,6 +130,45 @@ struct App {
     // Highlighting
     ps: SyntaxSet,
     theme: Theme,
+    theme_set: ThemeSet,
+    theme_names: Vec<String>,
+    theme_idx: usize,
+
+    // ANSI/raw-control handling
+    force_ansi: bool,
+
+    // Search
+    search_input_active: bool,
+    search_query: String,
+    search_matches: Vec<search::Match>,
+    search_current: Option<usize>,
+}
+
+impl App {
+    /// Highlight (and cache) whatever part of `[start, end)` isn't cached
+    /// yet, when the current file is going through the incremental syntect
+    /// path. Other modes (ansi, escaped, image) are cheap enough to have
+    /// rendered their full `highlighted_lines` up front.
+    fn prepare_visible_window(&mut self, start: usize, end: usize) {
+        let ps = &self.ps;
+        let theme = &self.theme;
+        if let Some(h) = self.highlighter.as_mut() {
+            h.ensure_range(ps, theme, start, end);
+        }
+    }
+
+    fn line_at(&self, idx: usize) -> Line<'static> {
+        if let Some(h) = &self.highlighter {
+            h.get(idx)
+                .cloned()
+                .unwrap_or_else(|| Line::from(Span::raw("")))
+        } else {
+            self.highlighted_lines
+                .get(idx)
+                .cloned()
+                .unwrap_or_else(|| Line::from(Span::raw("")))
+        }
+    }
 }
 
 fn main() -> Result<()> {
@@ -111,8 +201,16 @@ fn restore_terminal() -> Result<()> {
 }
 
 fn run(terminal: &mut Terminal<CrosstermBackend<Stdout>>, args: Args) -> Result<()> {
-    let exts = parse_exts(args.exts.as_deref());
-    let files = collect_files(&args.path, &exts, args.max_kb)
+    let cfg = config::load();
+
+    let speed_ms = args.speed_ms.or(cfg.speed_ms).unwrap_or(60);
+    let step = args.step.or(cfg.step).unwrap_or(1);
+    let max_kb = args.max_kb.or(cfg.max_kb).unwrap_or(512);
+    let exts_str = args.exts.clone().or_else(|| cfg.exts.clone());
+    let theme_name = args.theme.clone().or_else(|| cfg.theme.clone());
+
+    let exts = parse_exts(exts_str.as_deref());
+    let files = collect_files(&args.path, &exts, max_kb)
         .with_context(|| format!("collect files from {}", args.path.display()))?;
 
     if files.is_empty() {
@@ -120,7 +218,22 @@ fn run(terminal: &mut Terminal<CrosstermBackend<Stdout>>, args: Args) -> Result<
     }
 
     let ps = SyntaxSet::load_defaults_newlines();
-    let theme = pick_theme();
+    let theme_set = load_theme_set();
+    let theme_names: Vec<String> = {
+        let mut names: Vec<String> = theme_set.themes.keys().cloned().collect();
+        names.sort();
+        names
+    };
+    let theme_idx = theme_name
+        .as_deref()
+        .and_then(|n| theme_names.iter().position(|t| t == n))
+        .unwrap_or_else(|| {
+            theme_names
+                .iter()
+                .position(|t| t == "base16-ocean.dark")
+                .unwrap_or(0)
+        });
+    let theme = pick_theme(&theme_set, &theme_names[theme_idx]);
 
     let mut app = App {
         files,
@@ -128,15 +241,36 @@ fn run(terminal: &mut Terminal<CrosstermBackend<Stdout>>, args: Args) -> Result<
 
         current_path: PathBuf::new(),
         raw: String::new(),
+        total_lines: 0,
         highlighted_lines: Vec::new(),
+        highlighter: None,
         syntax_name: String::new(),
+        line_status: Vec::new(),
+        uses_syntect: false,
 
         scroll: 0,
         paused: false,
         status: String::new(),
 
+        is_image: false,
+        image_escape_payload: None,
+        image_payload_sent: false,
+        image_shown_at: Instant::now(),
+        image_dwell: Duration::from_millis(args.image_dwell_ms),
+        graphics_protocol: image_view::detect_protocol(),
+
         ps,
         theme,
+        theme_set,
+        theme_names,
+        theme_idx,
+
+        force_ansi: args.ansi,
+
+        search_input_active: false,
+        search_query: String::new(),
+        search_matches: Vec::new(),
+        search_current: None,
     };
 
     if args.random_start {
@@ -145,32 +279,105 @@ fn run(terminal: &mut Terminal<CrosstermBackend<Stdout>>, args: Args) -> Result<
 
     load_current(&mut app)?;
 
-    let tick = Duration::from_millis(args.speed_ms.max(5));
+    if let Some(query) = args.search.clone() {
+        app.search_query = query;
+        execute_search(&mut app)?;
+    }
+
+    let tick = Duration::from_millis(speed_ms.max(5));
     let mut last_tick = Instant::now();
+    let signal_rx = signals::spawn_listener().context("install SIGTSTP/SIGCONT handlers")?;
 
     loop {
+        while let Ok(event) = signal_rx.try_recv() {
+            match event {
+                signals::SignalEvent::Suspend => {
+                    restore_terminal()?;
+                    signals::stop_self();
+                }
+                signals::SignalEvent::Resume => {
+                    setup_terminal()?;
+                    terminal.clear()?;
+                    last_tick = Instant::now();
+                    // `terminal.clear()` wipes whatever the graphics
+                    // protocol had drawn directly to the screen, so it
+                    // needs retransmitting once the terminal comes back.
+                    app.image_payload_sent = false;
+                }
+            }
+        }
+
+        let view_height = terminal
+            .size()
+            .map(|a| (a.height as usize).saturating_sub(2))
+            .unwrap_or(1);
+        let win_start = app.scroll.min(app.total_lines.saturating_sub(1));
+        let win_end = (win_start + view_height).min(app.total_lines);
+        app.prepare_visible_window(win_start, win_end);
+
         terminal.draw(|f| ui(f, &app))?;
+        if !app.image_payload_sent {
+            present_image_overlay(&app)?;
+            app.image_payload_sent = true;
+        }
 
         // Input (non-blocking with timeout until next tick)
         let timeout = tick.saturating_sub(last_tick.elapsed());
         if event::poll(timeout)? {
             if let Event::Key(k) = event::read()? {
                 if k.kind == KeyEventKind::Press {
-                    match k.code {
-                        KeyCode::Char('q') => return Ok(()),
-                        KeyCode::Char(' ') => app.paused = !app.paused,
-                        KeyCode::Char('n') | KeyCode::Right => {
-                            next_file(&mut app, args.r#loop)?;
+                    if app.search_input_active {
+                        match k.code {
+                            KeyCode::Enter => {
+                                app.search_input_active = false;
+                                execute_search(&mut app)?;
+                            }
+                            KeyCode::Esc => {
+                                app.search_input_active = false;
+                                app.search_query.clear();
+                            }
+                            KeyCode::Backspace => {
+                                app.search_query.pop();
+                            }
+                            KeyCode::Char(c) => app.search_query.push(c),
+                            _ => {}
                         }
-                        KeyCode::Char('p') | KeyCode::Left => {
-                            prev_file(&mut app, args.r#loop)?;
+                    } else {
+                        match k.code {
+                            KeyCode::Char('q') => return Ok(()),
+                            KeyCode::Char(' ') => app.paused = !app.paused,
+                            KeyCode::Char('/') => {
+                                app.search_input_active = true;
+                                app.search_query.clear();
+                                app.paused = true;
+                            }
+                            KeyCode::Char('n') if !app.search_matches.is_empty() => {
+                                advance_match(&mut app, 1)?;
+                            }
+                            KeyCode::Char('N') if !app.search_matches.is_empty() => {
+                                advance_match(&mut app, -1)?;
+                            }
+                            KeyCode::Esc if !app.search_matches.is_empty() => {
+                                app.search_matches.clear();
+                                app.search_current = None;
+                                app.status = "Search cleared".to_string();
+                            }
+                            KeyCode::Char('n') | KeyCode::Right => {
+                                next_file(&mut app, args.r#loop)?;
+                            }
+                            KeyCode::Char('p') | KeyCode::Left => {
+                                prev_file(&mut app, args.r#loop)?;
+                            }
+                            KeyCode::Char('r') => {
+                                load_current(&mut app)?;
+                            }
+                            KeyCode::Char('t') => {
+                                cycle_theme(&mut app)?;
+                            }
+                            KeyCode::Home => app.scroll = 0,
+                            KeyCode::End => app.scroll = app.total_lines.saturating_sub(1),
+                            _ => {}
                         }
-                        KeyCode::Char('r') => {
-                            load_current(&mut app)?;
-                        }
-                        KeyCode::Home => app.scroll = 0,
-                        KeyCode::End => app.scroll = app.highlighted_lines.len().saturating_sub(1),
-                        _ => {}
                     }
                 }
             }
@@ -179,19 +386,47 @@ fn run(terminal: &mut Terminal<CrosstermBackend<Stdout>>, args: Args) -> Result<
         if last_tick.elapsed() >= tick {
             last_tick = Instant::now();
             if !app.paused {
-                app.scroll = app.scroll.saturating_add(args.step);
+                if app.is_image {
+                    // Images don't scroll; hold them for their dwell time.
+                    if app.image_shown_at.elapsed() >= app.image_dwell {
+                        next_file(&mut app, args.r#loop)?;
+                    }
+                } else {
+                    app.scroll = app.scroll.saturating_add(step);
 
-                // When file ends, move to next
-                if app.scroll >= app.highlighted_lines.len().saturating_sub(1) {
-                    next_file(&mut app, args.r#loop)?;
+                    // When file ends, move to next
+                    if app.scroll >= app.total_lines.saturating_sub(1) {
+                        next_file(&mut app, args.r#loop)?;
+                    }
                 }
             }
         }
     }
 }
 
+/// For terminals with a real graphics protocol, print the pre-encoded image
+/// payload directly to stdout once per file load; ratatui has no concept of
+/// inline images, so this bypasses its buffer entirely. The caller only
+/// invokes this the first time a given image is shown (see
+/// `image_payload_sent`) since retransmitting the whole payload every tick
+/// would both waste bandwidth and flicker against the redrawn text frame.
+/// Terminals without a graphics protocol render the half-block fallback
+/// through the normal `ui` path instead; with no payload this returns
+/// without writing anything.
+fn present_image_overlay(app: &App) -> Result<()> {
+    let Some(payload) = &app.image_escape_payload else {
+        return Ok(());
+    };
+    let mut stdout = io::stdout();
+    execute!(stdout, MoveTo(0, 2))?;
+    use std::io::Write;
+    write!(stdout, "{payload}").ok();
+    stdout.flush().ok();
+    Ok(())
+}
+
 fn ui(f: &mut ratatui::Frame, app: &App) {
-    let size = f.area();
+    let size = f.size();
 
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -218,21 +453,54 @@ fn ui(f: &mut ratatui::Frame, app: &App) {
 
     let view_height = chunks[1].height as usize;
 
-    let start = app.scroll.min(app.highlighted_lines.len().saturating_sub(1));
-    let end = (start + view_height).min(app.highlighted_lines.len());
+    let start = app.scroll.min(app.total_lines.saturating_sub(1));
+    let end = (start + view_height).min(app.total_lines);
+
+    let current_match_here = app.search_current.and_then(|i| app.search_matches.get(i));
+
+    // When a graphics protocol is driving the image (drawn directly to the
+    // terminal by `present_image_overlay`), leave the body blank instead of
+    // also rendering the half-block fallback art underneath it — drawing
+    // both causes visible flicker as the frame and the image fight over the
+    // same cells.
+    let using_graphics_overlay = app.is_image && app.image_escape_payload.is_some();
 
     let mut text = Text::default();
-    for line in &app.highlighted_lines[start..end] {
-        text.lines.push(line.clone());
+    if !using_graphics_overlay {
+        for idx in start..end {
+            let mut line = app.line_at(idx);
+            if let Some(m) = current_match_here {
+                if m.file_index == app.file_index && m.line == idx {
+                    line = search::highlight_range(&line, &m.range);
+                }
+            }
+            let status = app.line_status.get(idx).copied();
+            let mut spans = Vec::with_capacity(line.spans.len() + 1);
+            match status.and_then(git_gutter::gutter_span) {
+                Some((glyph, color)) => spans.push(Span::styled(glyph, Style::default().fg(color))),
+                None => spans.push(Span::raw(if app.line_status.is_empty() {
+                    ""
+                } else {
+                    "  "
+                })),
+            }
+            spans.extend(line.spans.iter().cloned());
+            text.lines.push(Line::from(spans));
+        }
     }
 
-    // If file is short, pad to avoid jitter
+    // If file is short (or the body is intentionally blank), pad to avoid
+    // layout jitter.
     while text.lines.len() < view_height {
         text.lines.push(Line::from(Span::raw("")));
     }
 
-    let footer_hint = if app.status.is_empty() {
-        "q quit • space pause • n/p next/prev • r reload • ←/→ also work"
+    let search_prompt;
+    let footer_hint = if app.search_input_active {
+        search_prompt = format!("/{}", app.search_query);
+        search_prompt.as_str()
+    } else if app.status.is_empty() {
+        "q quit • space pause • n/p next/prev • r reload • t theme • / search • ←/→ also work"
     } else {
         app.status.as_str()
     };
@@ -305,12 +573,15 @@ fn parse_exts(s: Option<&str>) -> HashSet<String> {
     let default = [
         "rs", "toml", "c", "h", "cpp", "hpp", "cc", "cs", "go", "py", "js", "ts", "jsx", "tsx",
         "java", "kt", "swift", "php", "rb", "lua", "sh", "ps1", "sql", "html", "css", "json",
-        "yml", "yaml", "md",
+        "yml", "yaml", "md", "ans", "log", "png", "jpg", "jpeg", "gif", "webp",
     ];
 
     let mut set = HashSet::new();
     let list: Vec<&str> = if let Some(s) = s {
-        s.split(',').map(|x| x.trim()).filter(|x| !x.is_empty()).collect()
+        s.split(',')
+            .map(|x| x.trim())
+            .filter(|x| !x.is_empty())
+            .collect()
     } else {
         default.to_vec()
     };
@@ -324,8 +595,20 @@ fn parse_exts(s: Option<&str>) -> HashSet<String> {
 fn load_current(app: &mut App) -> Result<()> {
     app.scroll = 0;
     app.status.clear();
+    app.is_image = false;
+    app.image_escape_payload = None;
 
     let path = app.files[app.file_index].clone();
+
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+    if image_view::is_image_ext(&ext) {
+        return load_current_image(app, path);
+    }
+
     let raw = match fs::read_to_string(&path) {
         Ok(s) => s,
         Err(e) => {
@@ -336,19 +619,70 @@ fn load_current(app: &mut App) -> Result<()> {
         }
     };
 
-    let syntax = pick_syntax(&app.ps, &path, &raw);
-    let syntax_name = syntax.name.clone();
+    let auto_ansi = matches!(ext.as_str(), "ans" | "log");
+
+    let (highlighted_lines, highlighter, syntax_name, uses_syntect, total_lines) =
+        if app.force_ansi || auto_ansi {
+            let lines = ansi::ansi_to_tui_lines(&raw);
+            let count = lines.len();
+            (lines, None, "ansi".to_string(), false, count)
+        } else if ansi::has_control_bytes(&raw) {
+            let lines = ansi::escape_to_tui_lines(&raw);
+            let count = lines.len();
+            (lines, None, "text (escaped)".to_string(), false, count)
+        } else {
+            let syntax = pick_syntax(&app.ps, &path, &raw);
+            let syntax_name = syntax.name.clone();
+            let h = highlight::IncrementalHighlighter::new(&raw, syntax, &app.theme);
+            let count = h.line_count();
+            (Vec::new(), Some(h), syntax_name, true, count)
+        };
+    app.uses_syntect = uses_syntect;
 
-    let highlighted = highlight_to_tui_lines(&app.ps, &app.theme, syntax, &raw);
+    app.line_status = git_gutter::line_statuses(&path, total_lines).unwrap_or_default();
 
     app.current_path = path;
     app.raw = raw;
-    app.highlighted_lines = highlighted;
+    app.total_lines = total_lines;
+    app.highlighted_lines = highlighted_lines;
+    app.highlighter = highlighter;
     app.syntax_name = syntax_name;
 
     Ok(())
 }
 
+fn load_current_image(app: &mut App, path: PathBuf) -> Result<()> {
+    // Rough viewport size; the exact frame size isn't known until the next
+    // `ui` call, but the body occupies the full width and all but 2 rows.
+    let (cols, rows) = crossterm::terminal::size().unwrap_or((80, 24));
+    let cell_w = cols;
+    let cell_h = rows.saturating_sub(2).max(1);
+
+    match image_view::load_image(&path, cell_w, cell_h, app.graphics_protocol) {
+        Ok(preview) => {
+            app.highlighted_lines = preview.fallback_lines;
+            app.image_escape_payload = preview.escape_payload;
+        }
+        Err(e) => {
+            app.status = format!("Skipping unreadable image: {} ({})", path.display(), e);
+            app.highlighted_lines = vec![Line::from(Span::raw(""))];
+        }
+    }
+    app.image_payload_sent = false;
+
+    app.is_image = true;
+    app.uses_syntect = false;
+    app.highlighter = None;
+    app.total_lines = app.highlighted_lines.len();
+    app.image_shown_at = Instant::now();
+    app.line_status.clear();
+    app.raw.clear();
+    app.syntax_name = "image".to_string();
+    app.current_path = path;
+
+    Ok(())
+}
+
 fn pick_syntax<'a>(ps: &'a SyntaxSet, path: &Path, raw: &str) -> &'a SyntaxReference {
     // Try extension first, then fallback to content-based, then plain text
     ps.find_syntax_for_file(path)
@@ -358,40 +692,101 @@ fn pick_syntax<'a>(ps: &'a SyntaxSet, path: &Path, raw: &str) -> &'a SyntaxRefer
         .unwrap_or_else(|| ps.find_syntax_plain_text())
 }
 
-fn pick_theme() -> Theme {
-    // Built-in themes; choose a high-contrast dark theme by default
-    // (ThemeSet::load_defaults() includes common ones like "base16-ocean.dark")
-    let ts = ThemeSet::load_defaults();
+/// Built-in syntect themes plus any `.tmTheme` files dropped into the user
+/// theme directory (Catppuccin, Dracula, etc.), so users aren't limited to
+/// syntect's bundled set.
+fn load_theme_set() -> ThemeSet {
+    let mut ts = ThemeSet::load_defaults();
+    if let Some(dir) = config::themes_dir() {
+        if dir.is_dir() {
+            ts.add_from_folder(&dir).ok();
+        }
+    }
+    ts
+}
+
+/// Look up `name` in the loaded theme set; fall back to the high-contrast
+/// dark default, then to whatever theme happens to be first.
+fn pick_theme(ts: &ThemeSet, name: &str) -> Theme {
     ts.themes
-        .get("base16-ocean.dark")
+        .get(name)
+        .or_else(|| ts.themes.get("base16-ocean.dark"))
         .cloned()
         .unwrap_or_else(|| ts.themes.values().next().cloned().unwrap())
 }
 
-fn highlight_to_tui_lines(
-    ps: &SyntaxSet,
-    theme: &Theme,
-    syntax: &SyntaxReference,
-    raw: &str,
-) -> Vec<Line<'static>> {
-    let mut h = HighlightLines::new(syntax, theme);
+/// Cycle `app.theme` to the next loaded theme and re-highlight the current
+/// file so the change is visible immediately, without reloading it from
+/// disk.
+fn cycle_theme(app: &mut App) -> Result<()> {
+    if app.theme_names.is_empty() {
+        return Ok(());
+    }
+    app.theme_idx = (app.theme_idx + 1) % app.theme_names.len();
+    app.theme = pick_theme(&app.theme_set, &app.theme_names[app.theme_idx]);
+    app.status = format!("Theme: {}", app.theme_names[app.theme_idx]);
 
-    let mut out = Vec::new();
-    for line in LinesWithEndings::from(raw) {
-        let regions = h.highlight_line(line, ps).unwrap_or_default();
-        let mut spans: Vec<Span<'static>> = Vec::with_capacity(regions.len());
+    if !app.uses_syntect {
+        return Ok(());
+    }
+    // The new theme invalidates every cached line and checkpoint, so start
+    // the incremental highlighter over; the visible window gets re-warmed
+    // on the next frame.
+    let syntax = pick_syntax(&app.ps, &app.current_path, &app.raw);
+    app.syntax_name = syntax.name.clone();
+    app.highlighter = Some(highlight::IncrementalHighlighter::new(
+        &app.raw, syntax, &app.theme,
+    ));
+    Ok(())
+}
 
-        for (style, text) in regions {
-            let fg = Color::Rgb(style.foreground.r, style.foreground.g, style.foreground.b);
-            spans.push(Span::styled(text.to_string(), Style::default().fg(fg)));
-        }
-        out.push(Line::from(spans));
+/// Run `app.search_query` against every collected file and jump to the
+/// nearest match at or after the current position.
+fn execute_search(app: &mut App) -> Result<()> {
+    app.search_matches =
+        search::search_all(&app.files, app.file_index, &app.raw, &app.search_query);
+    app.search_current = search::nearest_from(&app.search_matches, app.file_index, app.scroll);
+
+    if app.search_matches.is_empty() {
+        app.status = format!("No matches for \"{}\"", app.search_query);
+        return Ok(());
     }
 
-    if out.is_empty() {
-        out.push(Line::from(Span::raw("")));
+    let idx = app.search_current.unwrap_or(0);
+    jump_to_match(app, idx)
+}
+
+/// Move `delta` matches forward/backward (wrapping) and jump to it.
+fn advance_match(app: &mut App, delta: i64) -> Result<()> {
+    if app.search_matches.is_empty() {
+        return Ok(());
+    }
+    let len = app.search_matches.len() as i64;
+    let current = app.search_current.unwrap_or(0) as i64;
+    let next = (current + delta).rem_euclid(len) as usize;
+    jump_to_match(app, next)
+}
+
+/// Load the match's file if needed and center the viewport on its line.
+fn jump_to_match(app: &mut App, idx: usize) -> Result<()> {
+    let m = app.search_matches[idx].clone();
+    app.search_current = Some(idx);
+
+    if m.file_index != app.file_index {
+        app.file_index = m.file_index;
+        load_current(app)?;
     }
-    out
+
+    let (_, rows) = crossterm::terminal::size().unwrap_or((80, 24));
+    let view_height = (rows as usize).saturating_sub(2).max(1);
+    app.scroll = m.line.saturating_sub(view_height / 2);
+    app.status = format!(
+        "Match {}/{}: \"{}\" — n/N next/prev match, Esc clear search",
+        idx + 1,
+        app.search_matches.len(),
+        app.search_query
+    );
+    Ok(())
 }
 
 fn next_file(app: &mut App, looping: bool) -> Result<()> {
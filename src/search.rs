@@ -0,0 +1,173 @@
+//! In-file and cross-file search with jump-to-match, triggered by `/` (like
+//! ripgrep-backed navigation in file browsers). Matches are collected once
+//! per query as `(file_index, line, byte range)` triples so `n`/`N`
+//! navigation is just an index bump, not a re-scan.
+
+use ratatui::{
+    style::{Color, Modifier},
+    text::{Line, Span},
+};
+use std::{fs, ops::Range, path::PathBuf};
+
+#[derive(Clone)]
+pub struct Match {
+    pub file_index: usize,
+    pub line: usize,
+    pub range: Range<usize>,
+}
+
+/// Scan every file in `files` for `query`. `current_raw` is used in place
+/// of re-reading `files[current_index]` from disk, since it's already
+/// loaded in memory.
+pub fn search_all(
+    files: &[PathBuf],
+    current_index: usize,
+    current_raw: &str,
+    query: &str,
+) -> Vec<Match> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    let mut matches = Vec::new();
+    for (file_index, path) in files.iter().enumerate() {
+        let owned;
+        let content: &str = if file_index == current_index {
+            current_raw
+        } else {
+            owned = fs::read_to_string(path).unwrap_or_default();
+            &owned
+        };
+        for (line, text) in content.lines().enumerate() {
+            for (start, _) in text.match_indices(query) {
+                matches.push(Match {
+                    file_index,
+                    line,
+                    range: start..start + query.len(),
+                });
+            }
+        }
+    }
+    matches
+}
+
+/// Index of the nearest match at or after `(file_index, line)`, wrapping to
+/// the first match overall if none remain.
+pub fn nearest_from(matches: &[Match], file_index: usize, line: usize) -> Option<usize> {
+    if matches.is_empty() {
+        return None;
+    }
+    matches
+        .iter()
+        .position(|m| (m.file_index, m.line) >= (file_index, line))
+        .or(Some(0))
+}
+
+/// Overlay a highlighted background on the byte range `range` of `line`,
+/// splitting whichever syntect span(s) cover it while keeping their
+/// original foreground color everywhere else.
+pub fn highlight_range(line: &Line<'static>, range: &Range<usize>) -> Line<'static> {
+    let mut spans = Vec::with_capacity(line.spans.len() + 2);
+    let mut offset = 0usize;
+
+    for span in &line.spans {
+        let text = span.content.as_ref();
+        let span_start = offset;
+        let span_end = offset + text.len();
+        offset = span_end;
+
+        if range.end <= span_start || range.start >= span_end {
+            spans.push(span.clone());
+            continue;
+        }
+
+        let local_start = range.start.saturating_sub(span_start).min(text.len());
+        let local_end = range.end.saturating_sub(span_start).min(text.len());
+
+        if local_start > 0 {
+            spans.push(Span::styled(text[..local_start].to_string(), span.style));
+        }
+        spans.push(Span::styled(
+            text[local_start..local_end].to_string(),
+            span.style
+                .bg(Color::Yellow)
+                .fg(Color::Black)
+                .add_modifier(Modifier::BOLD),
+        ));
+        if local_end < text.len() {
+            spans.push(Span::styled(text[local_end..].to_string(), span.style));
+        }
+    }
+
+    Line::from(spans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain_text(line: &Line<'static>) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn search_all_finds_every_occurrence_in_the_current_file() {
+        let files = vec![PathBuf::from("current.rs")];
+        let raw = "fn foo() {\n    foo(foo());\n}\n";
+        let matches = search_all(&files, 0, raw, "foo");
+        assert_eq!(matches.len(), 3);
+        assert_eq!(matches[0].line, 0);
+        assert_eq!(matches[0].range, 3..6);
+        assert_eq!(matches[1].line, 1);
+        assert_eq!(matches[2].line, 1);
+    }
+
+    #[test]
+    fn search_all_returns_nothing_for_an_empty_query() {
+        let files = vec![PathBuf::from("current.rs")];
+        assert!(search_all(&files, 0, "foo foo foo", "").is_empty());
+    }
+
+    #[test]
+    fn nearest_from_wraps_to_the_first_match() {
+        let matches = vec![
+            Match {
+                file_index: 0,
+                line: 1,
+                range: 0..1,
+            },
+            Match {
+                file_index: 0,
+                line: 5,
+                range: 0..1,
+            },
+        ];
+        assert_eq!(nearest_from(&matches, 0, 0), Some(0));
+        assert_eq!(nearest_from(&matches, 0, 3), Some(1));
+        assert_eq!(nearest_from(&matches, 0, 10), Some(0));
+        assert!(nearest_from(&[], 0, 0).is_none());
+    }
+
+    #[test]
+    fn highlight_range_overlays_only_the_matched_bytes() {
+        let line = Line::from(Span::raw("the quick fox"));
+        let out = highlight_range(&line, &(4..9));
+        assert_eq!(plain_text(&out), "the quick fox");
+        // "quick" is isolated into its own highlighted span.
+        let quick = out
+            .spans
+            .iter()
+            .find(|s| s.content.as_ref() == "quick")
+            .expect("matched span present");
+        assert_eq!(quick.style.bg, Some(Color::Yellow));
+    }
+
+    #[test]
+    fn highlight_range_handles_multibyte_utf8_without_panicking() {
+        // "café" — 'é' is a 2-byte UTF-8 sequence; the match range ends
+        // mid-string but must still land on a char boundary.
+        let line = Line::from(Span::raw("café bar"));
+        let out = highlight_range(&line, &(0..5));
+        assert_eq!(plain_text(&out), "café bar");
+    }
+}
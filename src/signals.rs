@@ -0,0 +1,47 @@
+//! SIGTSTP/SIGCONT handling so Ctrl-Z suspends cleanly instead of leaving
+//! raw mode and the alternate screen engaged, the way `yazi` handles
+//! terminal suspend/resume.
+
+use anyhow::Result;
+use signal_hook::{
+    consts::{SIGCONT, SIGTSTP},
+    iterator::Signals,
+    low_level::emulate_default_handler,
+};
+use std::sync::mpsc::{self, Receiver};
+
+pub enum SignalEvent {
+    Suspend,
+    Resume,
+}
+
+/// Spawn a background thread listening for SIGTSTP/SIGCONT and forward them
+/// as `SignalEvent`s. Signal handlers can't safely touch the terminal
+/// directly, so the actual setup/teardown happens back on the main thread,
+/// in the event loop.
+pub fn spawn_listener() -> Result<Receiver<SignalEvent>> {
+    let mut signals = Signals::new([SIGTSTP, SIGCONT])?;
+    let (tx, rx) = mpsc::channel();
+
+    std::thread::spawn(move || {
+        for signal in signals.forever() {
+            let event = match signal {
+                SIGTSTP => SignalEvent::Suspend,
+                SIGCONT => SignalEvent::Resume,
+                _ => continue,
+            };
+            if tx.send(event).is_err() {
+                break;
+            }
+        }
+    });
+
+    Ok(rx)
+}
+
+/// Re-raise SIGTSTP with its default disposition, actually stopping the
+/// process (this call blocks until the shell resumes us with SIGCONT).
+/// Must only be called after the terminal has already been restored.
+pub fn stop_self() {
+    emulate_default_handler(SIGTSTP).ok();
+}